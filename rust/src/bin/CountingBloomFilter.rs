@@ -1,70 +1,450 @@
 use std::collections::HashSet;
-use std::hash::Hash;
-use std::time::Instant;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "serde")]
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-pub struct CountingBloomFilter {
-    counters: Vec<u32>,
+/// Computes the two base hashes used to derive all `num_hash_functions`
+/// slot indices for an item via double hashing (see `indices`).
+fn double_hash<T: Hash>(item: &T) -> (u64, u64) {
+    let mut h1 = DefaultHasher::new();
+    item.hash(&mut h1);
+    let h1 = h1.finish();
+
+    let mut h2 = DefaultHasher::new();
+    item.hash(&mut h2);
+    // Perturb the second hasher's state so h2 is independent of h1.
+    0x9e3779b97f4a7c15u64.hash(&mut h2);
+    let h2 = h2.finish();
+
+    (h1, h2)
+}
+
+/// Backing storage for a `CountingBloomFilter`'s per-slot counters.
+pub trait CounterStorage {
+    fn with_size(size: usize) -> Self;
+    fn get(&self, index: usize) -> u32;
+    /// Returns `true` if the counter at `index` was already saturated.
+    fn increment(&mut self, index: usize) -> bool;
+    fn decrement(&mut self, index: usize);
+}
+
+macro_rules! impl_counter_storage_for_uint {
+    ($name:ident, $uint:ty) => {
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name(Vec<$uint>);
+
+        impl CounterStorage for $name {
+            fn with_size(size: usize) -> Self {
+                $name(vec![0; size])
+            }
+
+            fn get(&self, index: usize) -> u32 {
+                self.0[index] as u32
+            }
+
+            fn increment(&mut self, index: usize) -> bool {
+                if self.0[index] == <$uint>::MAX {
+                    true
+                } else {
+                    self.0[index] += 1;
+                    false
+                }
+            }
+
+            fn decrement(&mut self, index: usize) {
+                self.0[index] = self.0[index].saturating_sub(1);
+            }
+        }
+    };
+}
+
+impl_counter_storage_for_uint!(U8Counters, u8);
+impl_counter_storage_for_uint!(U16Counters, u16);
+impl_counter_storage_for_uint!(U32Counters, u32);
+
+/// Packs two 4-bit counters per byte, for ~8x less memory than `U32Counters`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NibbleCounters(Vec<u8>);
+
+impl NibbleCounters {
+    fn nibble(&self, index: usize) -> u8 {
+        let byte = self.0[index / 2];
+        if index.is_multiple_of(2) {
+            byte & 0x0F
+        } else {
+            byte >> 4
+        }
+    }
+
+    fn set_nibble(&mut self, index: usize, value: u8) {
+        let byte_index = index / 2;
+        let shift = if index.is_multiple_of(2) { 0 } else { 4 };
+        let byte = self.0[byte_index];
+        self.0[byte_index] = (byte & !(0x0F << shift)) | ((value & 0x0F) << shift);
+    }
+}
+
+impl CounterStorage for NibbleCounters {
+    fn with_size(size: usize) -> Self {
+        NibbleCounters(vec![0; size.div_ceil(2)])
+    }
+
+    fn get(&self, index: usize) -> u32 {
+        self.nibble(index) as u32
+    }
+
+    fn increment(&mut self, index: usize) -> bool {
+        let current = self.nibble(index);
+        if current == 0x0F {
+            true
+        } else {
+            self.set_nibble(index, current + 1);
+            false
+        }
+    }
+
+    fn decrement(&mut self, index: usize) {
+        let current = self.nibble(index);
+        if current > 0 {
+            self.set_nibble(index, current - 1);
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CountingBloomFilter<C: CounterStorage = U32Counters> {
+    counters: C,
     size: usize,
     num_hash_functions: usize,
+    expected_items: usize,
+    overflowed: bool,
 }
 
-impl CountingBloomFilter {
+impl<C: CounterStorage> CountingBloomFilter<C> {
     pub fn new(size: usize, num_hash_functions: usize) -> Self {
         CountingBloomFilter {
-            counters: vec![0; size],
+            counters: C::with_size(size),
             size,
             num_hash_functions,
+            expected_items: 0,
+            overflowed: false,
         }
     }
 
+    /// Builds a filter sized for `expected_items` entries at a target `fp_rate`,
+    /// e.g. `with_fp_rate(100_000, 0.01)` for "100k items at 1% FP".
+    pub fn with_fp_rate(expected_items: usize, fp_rate: f64) -> Self {
+        assert!(expected_items > 0, "expected_items must be greater than 0");
+        assert!(
+            fp_rate > 0.0 && fp_rate < 1.0,
+            "fp_rate must be in (0, 1), got {}",
+            fp_rate
+        );
+
+        let n = expected_items as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let size = (-(n * fp_rate.ln()) / (ln2 * ln2)).ceil() as usize;
+        let num_hash_functions = (((size as f64) / n) * ln2).round().max(1.0) as usize;
+
+        let mut filter = Self::new(size, num_hash_functions);
+        filter.expected_items = expected_items;
+        filter
+    }
+
     pub fn add<T: Hash>(&mut self, item: &T) {
-        for i in 0..self.num_hash_functions {
-            let index = self.get_hash(item, i);
-            if self.counters[index] < u32::MAX {
-                self.counters[index] += 1;
+        let indices: Vec<usize> = self.indices(item).collect();
+        for index in indices {
+            if self.counters.increment(index) {
+                self.overflowed = true;
             }
         }
     }
 
     pub fn remove<T: Hash>(&mut self, item: &T) {
-        for i in 0..self.num_hash_functions {
-            let index = self.get_hash(item, i);
-            if self.counters[index] > 0 {
-                self.counters[index] -= 1;
-            }
+        let indices: Vec<usize> = self.indices(item).collect();
+        for index in indices {
+            self.counters.decrement(index);
         }
     }
 
     pub fn might_contain<T: Hash>(&self, item: &T) -> bool {
-        (0..self.num_hash_functions).all(|i| self.counters[self.get_hash(item, i)] > 0)
+        self.indices(item).all(|index| self.counters.get(index) > 0)
     }
 
-    fn get_hash<T: Hash>(&self, item: &T, i: usize) -> usize {
-        use std::hash::{Hash, Hasher};
-        use std::collections::hash_map::DefaultHasher;
-
-        let mut hasher = DefaultHasher::new();
-        item.hash(&mut hasher);
-        i.hash(&mut hasher);
-        let hash = hasher.finish();
+    /// Returns `true` if any counter has ever saturated. Once that happens,
+    /// `remove` can silently corrupt membership for items that share a slot
+    /// with the saturated one, since the true count is no longer tracked.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
 
-        let bytes = hash.to_le_bytes();
-        let digest = md5::compute(bytes);
-        let result = u64::from_le_bytes(digest[..8].try_into().unwrap());
-        (result as usize) % self.size
+    /// Derives the `num_hash_functions` slot indices for `item` using
+    /// Kirsch-Mitzenmacher double hashing: two base hashes `h1`/`h2` are
+    /// computed once, and the i-th index is `(h1 + i * h2) % size`. This
+    /// gives the false-positive behavior of `k` independent hashes for the
+    /// cost of two.
+    fn indices<T: Hash>(&self, item: &T) -> impl Iterator<Item = usize> + '_ {
+        let (h1, h2) = double_hash(item);
+        (0..self.num_hash_functions)
+            .map(move |i| (h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.size as u64) as usize)
     }
 
     pub fn get_estimated_false_positive_rate(&self, num_items: usize) -> f64 {
         (1.0 - (-((self.num_hash_functions as f64) * (num_items as f64) / (self.size as f64))).exp())
             .powi(self.num_hash_functions as i32)
     }
+
+    /// Like `get_estimated_false_positive_rate`, using the `expected_items` from `with_fp_rate`.
+    pub fn get_estimated_false_positive_rate_for_expected_items(&self) -> f64 {
+        self.get_estimated_false_positive_rate(self.expected_items)
+    }
+
+    /// Number of slots with a nonzero counter.
+    pub fn count_nonzero(&self) -> usize {
+        (0..self.size).filter(|&i| self.counters.get(i) > 0).count()
+    }
+
+    /// Sum of all counter values, i.e. the total multiplicity stored.
+    pub fn sum_counters(&self) -> u64 {
+        (0..self.size).map(|i| self.counters.get(i) as u64).sum()
+    }
+
+    /// Estimates the distinct item count from the fill state: `n ≈ -(m / k) * ln(1 - X / m)`.
+    pub fn estimated_item_count(&self) -> f64 {
+        let m = self.size as f64;
+        let k = self.num_hash_functions as f64;
+        let x = self.count_nonzero() as f64;
+        -(m / k) * (1.0 - x / m).ln()
+    }
+
+    /// Persists the filter so `load_from` can restore an identical `might_contain`.
+    #[cfg(feature = "serde")]
+    pub fn save_to<W: Write>(&self, w: W) -> io::Result<()>
+    where
+        Self: Serialize,
+    {
+        bincode::serialize_into(w, self).map_err(io::Error::other)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load_from<R: Read>(r: R) -> io::Result<Self>
+    where
+        Self: for<'de> Deserialize<'de>,
+    {
+        bincode::deserialize_from(r).map_err(io::Error::other)
+    }
+}
+
+/// A thread-safe `CountingBloomFilter`, sharded by the top bits of `h1` so
+/// unrelated items rarely contend for the same lock. `might_contain` reads
+/// a consistent snapshot of a single shard under its read lock; `add`/`remove`
+/// take that shard's write lock. A `remove` never causes a false negative for
+/// an item whose `add` happened-before it.
+pub struct ConcurrentCountingBloomFilter<C: CounterStorage = U32Counters> {
+    shards: Vec<RwLock<CountingBloomFilter<C>>>,
+}
+
+impl<C: CounterStorage> ConcurrentCountingBloomFilter<C> {
+    /// Splits `size` counters evenly across `num_shards` independently locked shards.
+    pub fn new(size: usize, num_hash_functions: usize, num_shards: usize) -> Self {
+        assert!(num_shards > 0, "num_shards must be at least 1");
+        let shard_size = size.div_ceil(num_shards);
+        let shards = (0..num_shards)
+            .map(|_| RwLock::new(CountingBloomFilter::new(shard_size, num_hash_functions)))
+            .collect();
+
+        ConcurrentCountingBloomFilter { shards }
+    }
+
+    pub fn add<T: Hash>(&self, item: &T) {
+        let shard = self.shard_for(item);
+        self.shards[shard].write().unwrap().add(item);
+    }
+
+    pub fn remove<T: Hash>(&self, item: &T) {
+        let shard = self.shard_for(item);
+        self.shards[shard].write().unwrap().remove(item);
+    }
+
+    pub fn might_contain<T: Hash>(&self, item: &T) -> bool {
+        let shard = self.shard_for(item);
+        self.shards[shard].read().unwrap().might_contain(item)
+    }
+
+    fn shard_for<T: Hash>(&self, item: &T) -> usize {
+        let (h1, _) = double_hash(item);
+        ((h1 >> 32) % self.shards.len() as u64) as usize
+    }
+}
+
+/// A time-windowed membership filter for "seen in the last N" use cases
+/// (e.g. nodes that recently timed out), where entries age out automatically
+/// instead of requiring an explicit `remove`.
+///
+/// Each slot stores a small `u16` tag relative to `base_generation`, which is
+/// periodically rebased forward so the tag never has to represent more than
+/// `window` generations of history — keeping memory fixed and small (like
+/// the `u8`/`u16` `CounterStorage` widths) without the wraparound that would
+/// let a bare wrapping counter resurrect expired slots. A slot counts as
+/// occupied only while its real generation is within `window` generations of
+/// "now"; older slots are implicitly expired and get reclaimed lazily the
+/// next insert that happens to hash to them, or eagerly during a rebase.
+/// "Now" advances ("rolls") after `roll_count` inserts or after
+/// `roll_interval` elapses, whichever comes first; `contains` also accounts
+/// for elapsed wall-clock time on its own, so entries still expire in a
+/// filter that is only ever read after the last insert.
+///
+/// This makes retention a fixed cliff, not a true sliding window: an item
+/// inserted right before a roll expires after as little as `(window - 1)`
+/// roll periods, while one inserted right after a roll can last up to
+/// `window` roll periods. Pick `window` and `roll_count`/`roll_interval`
+/// so that lower bound is still acceptable for your use case.
+pub struct RollingBloomFilter<T> {
+    generations: Vec<u16>,
+    size: usize,
+    num_hash_functions: usize,
+    window: u64,
+    base_generation: u64,
+    current_generation: u64,
+    roll_count: usize,
+    inserted_since_last_roll: usize,
+    roll_interval: Duration,
+    last_roll: Instant,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Hash> RollingBloomFilter<T> {
+    /// `window` is how many of the most recent generations count as "present";
+    /// larger values smooth the hard-cliff expiry closer to a true sliding
+    /// window at the cost of holding stale entries longer. Must fit in a `u16`
+    /// tag with room to spare, since slots are stored relative to a rebased base.
+    pub fn new(
+        size: usize,
+        num_hash_functions: usize,
+        window: u64,
+        roll_count: usize,
+        roll_interval: Duration,
+    ) -> Self {
+        assert!(window >= 1, "window must be at least 1");
+        assert!(
+            window < u16::MAX as u64 / 2,
+            "window must leave headroom in the u16 generation tag"
+        );
+
+        RollingBloomFilter {
+            generations: vec![0; size],
+            size,
+            num_hash_functions,
+            window,
+            base_generation: 0,
+            current_generation: 1,
+            roll_count,
+            inserted_since_last_roll: 0,
+            roll_interval,
+            last_roll: Instant::now(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn insert(&mut self, item: &T) {
+        self.maybe_roll();
+
+        let tag = (self.current_generation - self.base_generation + 1) as u16;
+        let indices: Vec<usize> = self.indices(item).collect();
+        for index in indices {
+            self.generations[index] = tag;
+        }
+        self.inserted_since_last_roll += 1;
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        let now = self.effective_generation();
+        self.indices(item).all(|index| self.is_live(self.generations[index], now))
+    }
+
+    fn is_live(&self, tag: u16, now: u64) -> bool {
+        if tag == 0 {
+            return false;
+        }
+        let real_generation = self.base_generation + (tag - 1) as u64;
+        now - real_generation < self.window
+    }
+
+    fn indices(&self, item: &T) -> impl Iterator<Item = usize> + '_ {
+        let (h1, h2) = double_hash(item);
+        (0..self.num_hash_functions)
+            .map(move |i| (h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.size as u64) as usize)
+    }
+
+    /// Number of whole `roll_interval`s that have elapsed since `last_roll`
+    /// but haven't yet been folded into `current_generation` by a write.
+    fn pending_time_rolls(&self) -> u64 {
+        if self.roll_interval.is_zero() {
+            return 0;
+        }
+        (self.last_roll.elapsed().as_nanos() / self.roll_interval.as_nanos().max(1)) as u64
+    }
+
+    /// `current_generation` as of right now, including roll_interval-driven
+    /// rolls that haven't been materialized by a write yet. Used by the
+    /// read-only `contains` path so entries expire even without new inserts.
+    fn effective_generation(&self) -> u64 {
+        self.current_generation + self.pending_time_rolls()
+    }
+
+    fn maybe_roll(&mut self) {
+        let time_rolls = self.pending_time_rolls();
+        if self.inserted_since_last_roll >= self.roll_count || time_rolls >= 1 {
+            self.roll(time_rolls.max(1));
+        }
+    }
+
+    fn roll(&mut self, rolls: u64) {
+        self.current_generation += rolls;
+        self.inserted_since_last_roll = 0;
+        self.last_roll = Instant::now();
+
+        if self.current_generation - self.base_generation + self.window >= u16::MAX as u64 {
+            self.rebase();
+        }
+    }
+
+    /// Shifts `base_generation` forward so live slots' tags stay small, fixing
+    /// up every slot's tag (or clearing it if it's now expired) in one pass.
+    fn rebase(&mut self) {
+        let new_base = self.current_generation.saturating_sub(self.window - 1);
+        let old_base = self.base_generation;
+        let current = self.current_generation;
+        let window = self.window;
+
+        for tag in self.generations.iter_mut() {
+            if *tag == 0 {
+                continue;
+            }
+            let real_generation = old_base + (*tag - 1) as u64;
+            *tag = if current - real_generation < window {
+                (real_generation - new_base + 1) as u16
+            } else {
+                0
+            };
+        }
+
+        self.base_generation = new_base;
+    }
 }
 
 fn main() {
     let size = 1_000_000;
     let num_hash_functions = 5;
-    let mut filter = CountingBloomFilter::new(size, num_hash_functions);
+    let mut filter: CountingBloomFilter = CountingBloomFilter::new(size, num_hash_functions);
 
     let num_items = 100_000;
     let mut added_items = HashSet::new();
@@ -135,3 +515,215 @@ fn main() {
         false_negatives as f64 / num_removals as f64 * 100.0
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_might_contain_distinct_items_without_trivial_collisions() {
+        let mut filter: CountingBloomFilter = CountingBloomFilter::new(10_000, 4);
+        let items: Vec<u64> = (0..500).collect();
+        for item in &items {
+            filter.add(item);
+        }
+        for item in &items {
+            assert!(filter.might_contain(item));
+        }
+
+        let false_positives = (10_000..11_000)
+            .filter(|candidate: &u64| filter.might_contain(candidate))
+            .count();
+        // With m=10_000, k=4, n=500 the expected FP rate is about 0.1%;
+        // allow generous slack so the test isn't flaky while still catching
+        // a badly broken hash (e.g. one that collapses to a single bucket).
+        assert!(
+            false_positives < 200,
+            "unexpectedly high false positive count: {}",
+            false_positives
+        );
+    }
+
+    #[test]
+    fn remove_clears_membership_for_non_colliding_items() {
+        let mut filter: CountingBloomFilter = CountingBloomFilter::new(10_000, 4);
+        filter.add(&1u64);
+        filter.add(&2u64);
+        assert!(filter.might_contain(&1u64));
+        assert!(filter.might_contain(&2u64));
+
+        filter.remove(&1u64);
+        assert!(!filter.might_contain(&1u64));
+        assert!(filter.might_contain(&2u64));
+    }
+
+    #[test]
+    fn u8_counters_overflow_sets_overflowed_flag() {
+        let mut filter: CountingBloomFilter<U8Counters> = CountingBloomFilter::new(1000, 1);
+        assert!(!filter.overflowed());
+
+        // u8 saturates at 255; adding the same item 256 times pins every
+        // slot it hashes to at u8::MAX instead of wrapping.
+        for _ in 0..256 {
+            filter.add(&42u64);
+        }
+        assert!(filter.overflowed());
+        assert!(filter.might_contain(&42u64));
+    }
+
+    #[test]
+    fn nibble_counters_overflow_sets_overflowed_flag() {
+        let mut filter: CountingBloomFilter<NibbleCounters> = CountingBloomFilter::new(1000, 1);
+        assert!(!filter.overflowed());
+
+        // A nibble saturates at 15; adding the same item 16 times pins it.
+        for _ in 0..16 {
+            filter.add(&7u64);
+        }
+        assert!(filter.overflowed());
+        assert!(filter.might_contain(&7u64));
+    }
+
+    #[test]
+    fn counter_storage_does_not_overflow_below_saturation() {
+        let mut filter: CountingBloomFilter<U8Counters> = CountingBloomFilter::new(1000, 1);
+        for _ in 0..10 {
+            filter.add(&1u64);
+        }
+        assert!(!filter.overflowed());
+    }
+
+    #[test]
+    fn with_fp_rate_derives_sane_size_and_hash_count() {
+        let filter: CountingBloomFilter = CountingBloomFilter::with_fp_rate(100_000, 0.01);
+        // m = ceil(-(n * ln(p)) / (ln 2)^2) for n=100_000, p=0.01 is ~958506.
+        assert!(
+            (900_000..1_000_000).contains(&filter.size),
+            "unexpected size: {}",
+            filter.size
+        );
+        // k = round((m / n) * ln 2) is ~7 for these parameters.
+        assert!(
+            (5..=9).contains(&filter.num_hash_functions),
+            "unexpected num_hash_functions: {}",
+            filter.num_hash_functions
+        );
+        assert!(filter.get_estimated_false_positive_rate_for_expected_items() < 0.02);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected_items must be greater than 0")]
+    fn with_fp_rate_rejects_zero_expected_items() {
+        let _: CountingBloomFilter = CountingBloomFilter::with_fp_rate(0, 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "fp_rate must be in (0, 1)")]
+    fn with_fp_rate_rejects_out_of_range_fp_rate() {
+        let _: CountingBloomFilter = CountingBloomFilter::with_fp_rate(1000, 1.5);
+    }
+
+    #[test]
+    fn rolling_bloom_filter_contains_recent_insert() {
+        let mut filter: RollingBloomFilter<u64> =
+            RollingBloomFilter::new(1000, 3, 2, 100, Duration::from_secs(3600));
+        filter.insert(&42u64);
+        assert!(filter.contains(&42u64));
+        assert!(!filter.contains(&43u64));
+    }
+
+    #[test]
+    fn rolling_bloom_filter_expires_outside_window() {
+        let mut filter: RollingBloomFilter<u64> =
+            RollingBloomFilter::new(1000, 3, 1, 1, Duration::from_secs(3600));
+        filter.insert(&42u64);
+        assert!(filter.contains(&42u64));
+
+        // Force two rolls (roll_count = 1) without reinserting 42.
+        filter.insert(&1u64);
+        filter.insert(&2u64);
+        assert!(!filter.contains(&42u64));
+    }
+
+    #[test]
+    fn rolling_bloom_filter_expires_on_read_without_further_inserts() {
+        let mut filter: RollingBloomFilter<u64> =
+            RollingBloomFilter::new(1000, 3, 1, 1_000_000, Duration::from_millis(50));
+        filter.insert(&42u64);
+        assert!(filter.contains(&42u64));
+
+        std::thread::sleep(Duration::from_millis(200));
+        // No further inserts happen, so only the roll_interval can expire this;
+        // contains() must account for elapsed time on its own.
+        assert!(!filter.contains(&42u64));
+    }
+
+    #[test]
+    fn concurrent_filter_add_and_might_contain() {
+        let filter: ConcurrentCountingBloomFilter = ConcurrentCountingBloomFilter::new(1000, 3, 4);
+        filter.add(&42u64);
+        assert!(filter.might_contain(&42u64));
+
+        filter.remove(&42u64);
+        assert!(!filter.might_contain(&42u64));
+    }
+
+    #[test]
+    fn concurrent_filter_handles_parallel_adds_from_many_threads() {
+        let filter: std::sync::Arc<ConcurrentCountingBloomFilter> =
+            std::sync::Arc::new(ConcurrentCountingBloomFilter::new(10_000, 4, 8));
+
+        let handles: Vec<_> = (0..8u64)
+            .map(|thread_id| {
+                let filter = std::sync::Arc::clone(&filter);
+                std::thread::spawn(move || {
+                    for i in 0..100u64 {
+                        filter.add(&(thread_id * 1000 + i));
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for thread_id in 0..8u64 {
+            for i in 0..100u64 {
+                assert!(filter.might_contain(&(thread_id * 1000 + i)));
+            }
+        }
+    }
+
+    #[test]
+    fn cardinality_estimation_from_fill_state() {
+        let mut filter: CountingBloomFilter = CountingBloomFilter::new(10_000, 4);
+        for i in 0..1000u64 {
+            filter.add(&i);
+        }
+
+        assert_eq!(filter.sum_counters(), (1000 * 4) as u64);
+        assert!(filter.count_nonzero() > 0 && filter.count_nonzero() <= 10_000);
+
+        let estimated = filter.estimated_item_count();
+        assert!(
+            (estimated - 1000.0).abs() < 100.0,
+            "expected estimate near 1000, got {}",
+            estimated
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut filter: CountingBloomFilter = CountingBloomFilter::new(1000, 3);
+        filter.add(&42u64);
+
+        let mut bytes = Vec::new();
+        filter.save_to(&mut bytes).unwrap();
+
+        let reloaded: CountingBloomFilter = CountingBloomFilter::load_from(&bytes[..]).unwrap();
+        assert!(reloaded.might_contain(&42u64));
+        assert!(!reloaded.might_contain(&43u64));
+    }
+}